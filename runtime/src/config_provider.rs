@@ -0,0 +1,37 @@
+//! Node-local runtime configuration provider.
+//!
+//! Some network defaults (the consensus minimum gas price and the address allowed to govern gas
+//! parameters) used to be frozen at compile time. This provider reads them from the node's local
+//! runtime configuration — the operator's `local.toml`, delivered through the SDK's local-config
+//! surface ([`Context::local_config`]) — and the runtime applies them in `migrate_state`, leaving
+//! the on-chain values in place when the node provides no value. `migrate_state` runs only when the
+//! runtime's `STATE_VERSION` changes, so a value changed in `local.toml` takes effect at the next
+//! such upgrade, not at node startup.
+//!
+//! Values that the SDK only exposes at compile time (the EVM chain id, the denomination decimals)
+//! or before any runtime context exists (the consensus trust root, read by the SGX verifier) cannot
+//! be sourced from this surface and remain compiled constants.
+//!
+//! [`Context::local_config`]: oasis_runtime_sdk::Context::local_config
+use oasis_runtime_sdk::{types::address::Address, Context};
+
+/// Configuration section name under which the runtime's local configuration is keyed in the node's
+/// `local.toml`.
+const LOCAL_CONFIG_MODULE: &str = "sapphire";
+
+/// Node-local runtime configuration, deserialized from the SDK local-config surface.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LocalConfig {
+    /// Override for the consensus minimum gas price of the native denomination.
+    pub min_gas_price: Option<u128>,
+    /// Address permitted to stage gas-parameter updates (governance/owner).
+    pub gas_governance_address: Option<Address>,
+}
+
+/// Load the node-local runtime configuration from the SDK local-config surface, defaulting to an
+/// empty configuration when the node provides no `sapphire` section (every field then falls back to
+/// its compiled default).
+pub fn local_config<C: Context>(ctx: &C) -> LocalConfig {
+    ctx.local_config(LOCAL_CONFIG_MODULE).unwrap_or_default()
+}
@@ -0,0 +1,155 @@
+//! Configurable fee split routing a portion of collected fees to the block producer.
+//!
+//! Sapphire collects transaction fees into the runtime's fee accumulator and pays block producers
+//! purely from the `modules::rewards` schedule. Borrowing the EOS-EVM "miner cut" idea, this module
+//! diverts a governed fraction of each block's collected fees — `miner_cut` basis points, taken
+//! from the live [`gas_params`] active set — directly to the entity that executed the batch. The
+//! remainder flows to the existing rewards/common pool as before.
+//!
+//! The cut defaults to `0` (see [`gas_params::GasParameters::miner_cut`]), preserving the previous
+//! behavior. It is computed from the fees collected *this block* — the growth of the fee
+//! accumulator between `begin_block` and `end_block` — rather than from its whole balance, so fees
+//! left unsettled from earlier blocks are not taxed again.
+//!
+//! [`gas_params`]: crate::gas_params
+use std::marker::PhantomData;
+
+use oasis_runtime_sdk::{
+    modules,
+    state::CurrentState,
+    storage,
+    types::{
+        address::Address,
+        token::{BaseUnits, Denomination},
+    },
+    Context,
+};
+
+use crate::gas_params;
+
+/// Module name, used as the storage prefix for the per-block snapshot.
+const MODULE_NAME: &str = "feeSplit";
+/// State key holding the fee-accumulator balance snapshotted at the start of the current block.
+const OPENING_BALANCE: &[u8] = &[0x01];
+
+/// Fee-split handler. Runs at the end of each block, before fees are swept into the common pool, so
+/// the miner cut is removed from the same total the rewards module later draws against.
+pub struct Module<Cfg: gas_params::Config> {
+    _cfg: PhantomData<Cfg>,
+}
+
+impl<Cfg: gas_params::Config> Module<Cfg> {
+    /// Read the native balance of the fee accumulator, treating any lookup error as zero so a block
+    /// handler never aborts block production.
+    fn accumulator_balance() -> u128 {
+        modules::accounts::Module::get_balance(
+            *modules::accounts::ADDRESS_FEE_ACCUMULATOR,
+            Denomination::NATIVE,
+        )
+        .unwrap_or(0)
+    }
+
+    /// Snapshot the fee accumulator's opening balance so `distribute` can measure only the fees
+    /// collected during this block, not any residue left over from prior blocks.
+    fn snapshot_opening_balance() {
+        let opening = Self::accumulator_balance();
+        CurrentState::with_store(|store| {
+            let store = storage::PrefixStore::new(store, &MODULE_NAME);
+            let mut tstore = storage::TypedStore::new(store);
+            tstore.insert(OPENING_BALANCE, opening);
+        });
+    }
+
+    fn opening_balance() -> u128 {
+        CurrentState::with_store(|store| {
+            let store = storage::PrefixStore::new(store, &MODULE_NAME);
+            let tstore = storage::TypedStore::new(store);
+            tstore.get(OPENING_BALANCE).unwrap_or(0)
+        })
+    }
+
+    /// Split the block's collected fees: credit `miner_cut` bps to the proposer and leave the
+    /// remainder in the fee accumulator for the rewards/common-pool settlement.
+    ///
+    /// Best-effort: if the proposer cannot be resolved or the transfer fails, the cut is skipped and
+    /// the fees flow to the common pool exactly as they did before this feature. A block handler
+    /// must never panic, so failures degrade rather than abort block production.
+    fn distribute<C: Context>(ctx: &C) {
+        let miner_cut = gas_params::Module::<Cfg>::active_params().miner_cut;
+        if miner_cut == 0 {
+            return;
+        }
+
+        // The cut is a fraction of the fees collected *this block*, not of the accumulator's whole
+        // balance: the accumulator may still hold fees from earlier blocks that have not yet been
+        // settled into the common pool, and taking a fresh cut from those each block would over-pay
+        // the proposer. Measure the delta against the opening balance snapshotted in `begin_block`.
+        let opening = Self::opening_balance();
+        let closing = Self::accumulator_balance();
+        let collected = closing.saturating_sub(opening);
+        if collected == 0 {
+            return;
+        }
+
+        // Fraction of the block's collected fees, rounded down so the cut never exceeds them.
+        let cut = collected.saturating_mul(miner_cut as u128) / 10_000;
+        if cut == 0 {
+            return;
+        }
+
+        // Pay the proposer (the entity that executed the batch). When no proposer can be resolved
+        // the cut is left untouched so it falls through to the common pool rather than being burnt.
+        let Some(proposer) = proposer_address(ctx) else {
+            return;
+        };
+        let amount = BaseUnits::new(cut, Denomination::NATIVE);
+        let _ = modules::accounts::Module::transfer(
+            *modules::accounts::ADDRESS_FEE_ACCUMULATOR,
+            proposer,
+            &amount,
+        );
+    }
+}
+
+/// Resolve the address of the block proposer from the runtime round results, mirroring how the
+/// rewards module attributes good compute work to consensus entities.
+fn proposer_address<C: Context>(ctx: &C) -> Option<Address> {
+    ctx.runtime_round_results()
+        .good_compute_entities
+        .first()
+        .map(Address::from_consensus_pk)
+}
+
+impl<Cfg: gas_params::Config> oasis_runtime_sdk::module::Module for Module<Cfg> {
+    const NAME: &'static str = "feeSplit";
+    const VERSION: u32 = 1;
+    type Error = std::convert::Infallible;
+    type Event = ();
+    type Parameters = ();
+    type Genesis = ();
+}
+
+impl<Cfg: gas_params::Config> oasis_runtime_sdk::module::BlockHandler for Module<Cfg> {
+    fn begin_block<C: Context>(_ctx: &C) {
+        Self::snapshot_opening_balance();
+    }
+
+    fn end_block<C: Context>(ctx: &C) {
+        Self::distribute(ctx);
+    }
+}
+
+impl<Cfg: gas_params::Config> oasis_runtime_sdk::module::MigrationHandler for Module<Cfg> {
+    type Genesis = ();
+
+    fn init_or_migrate<C: Context>(
+        _ctx: &C,
+        _meta: &mut modules::core::types::Metadata,
+        _genesis: Self::Genesis,
+    ) -> bool {
+        false
+    }
+}
+
+impl<Cfg: gas_params::Config> oasis_runtime_sdk::module::TransactionHandler for Module<Cfg> {}
+impl<Cfg: gas_params::Config> oasis_runtime_sdk::module::InvariantHandler for Module<Cfg> {}
@@ -0,0 +1,339 @@
+//! On-chain governed gas parameters with two-phase promotion.
+//!
+//! This module lets a privileged address retune the runtime's gas economics live, without shipping
+//! a new binary or bumping `STATE_VERSION`. An *active* parameter set is used for all fee
+//! computation, while a *pending* set can be staged together with a target activation round. At the
+//! start of each block the module promotes the pending set to active once the target round is
+//! reached, giving wallets and clients a known delay to adapt.
+//!
+//! Updates are partial: every field is an `Option` and unspecified fields inherit the current active
+//! value. Supplied values are range-checked before a pending set is stored.
+use oasis_runtime_sdk::{
+    self as sdk,
+    core::common::cbor,
+    module::{self, Module as _},
+    modules,
+    state::CurrentState,
+    storage,
+    types::address::Address,
+    Context,
+};
+
+/// Unique module name.
+const MODULE_NAME: &str = "gasParams";
+
+/// Errors emitted by the gas-parameters module.
+#[derive(Debug, thiserror::Error, sdk::Error)]
+pub enum Error {
+    #[error("invalid argument")]
+    #[sdk_error(code = 1)]
+    InvalidArgument,
+
+    #[error("forbidden")]
+    #[sdk_error(code = 2)]
+    Forbidden,
+
+    #[error("parameter out of range: {0}")]
+    #[sdk_error(code = 3)]
+    OutOfRange(&'static str),
+
+    #[error("core: {0}")]
+    #[sdk_error(transparent)]
+    Core(#[from] modules::core::Error),
+}
+
+/// Events emitted by the gas-parameters module.
+#[derive(Debug, cbor::Encode, sdk::Event)]
+#[cbor(untagged)]
+pub enum Event {
+    /// A pending parameter set was staged for a future activation round.
+    #[sdk_event(code = 1)]
+    Staged { active_round: u64 },
+
+    /// A pending parameter set was promoted to active.
+    #[sdk_event(code = 2)]
+    Promoted { round: u64 },
+}
+
+/// Overridable gas knobs. Each field is optional so updates may be partial; unspecified fields
+/// inherit the current active value.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct GasParameterUpdate {
+    #[cbor(optional)]
+    pub min_gas_price: Option<u128>,
+
+    #[cbor(optional)]
+    pub storage_byte: Option<u64>,
+
+    #[cbor(optional)]
+    pub consensus_deposit: Option<u64>,
+
+    #[cbor(optional)]
+    pub consensus_withdraw: Option<u64>,
+
+    /// Fraction of collected transaction fees, in basis points, diverted to the block proposer.
+    #[cbor(optional)]
+    pub miner_cut: Option<u16>,
+}
+
+/// A fully-resolved set of governed gas parameters.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct GasParameters {
+    pub min_gas_price: u128,
+    pub storage_byte: u64,
+    pub consensus_deposit: u64,
+    pub consensus_withdraw: u64,
+    /// Fraction of collected transaction fees, in basis points, diverted to the block proposer.
+    /// `0` disables the miner cut (the default), routing all fees to the rewards/common pool.
+    pub miner_cut: u16,
+}
+
+impl GasParameters {
+    /// Apply a partial update on top of `self`, returning the merged set. Supplied values are
+    /// range-checked; unspecified fields are inherited.
+    fn merge(&self, update: GasParameterUpdate) -> Result<Self, Error> {
+        let merged = Self {
+            min_gas_price: update.min_gas_price.unwrap_or(self.min_gas_price),
+            storage_byte: update.storage_byte.unwrap_or(self.storage_byte),
+            consensus_deposit: update.consensus_deposit.unwrap_or(self.consensus_deposit),
+            consensus_withdraw: update.consensus_withdraw.unwrap_or(self.consensus_withdraw),
+            miner_cut: update.miner_cut.unwrap_or(self.miner_cut),
+        };
+        merged.validate()?;
+        Ok(merged)
+    }
+
+    /// Validate that all knobs are within acceptable ranges.
+    fn validate(&self) -> Result<(), Error> {
+        if self.min_gas_price == 0 {
+            return Err(Error::OutOfRange("min_gas_price"));
+        }
+        // The storage byte cost must stay within the bounds assumed by fee estimation.
+        if self.storage_byte == 0 || self.storage_byte > 1_000 {
+            return Err(Error::OutOfRange("storage_byte"));
+        }
+        // The miner cut is a fraction of the whole, so it cannot exceed 100% (10_000 bps).
+        if self.miner_cut > 10_000 {
+            return Err(Error::OutOfRange("miner_cut"));
+        }
+        Ok(())
+    }
+}
+
+/// A pending parameter set staged for promotion at `active_round`.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct Pending {
+    pub active_round: u64,
+    pub params: GasParameters,
+}
+
+/// Parameters for the gas-parameters module.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct Parameters {
+    /// Address permitted to stage parameter updates (governance/owner). When `None`, no address can
+    /// stage updates and the active set can only change via a binary upgrade.
+    #[cbor(optional)]
+    pub governance_address: Option<Address>,
+    /// The currently active gas parameters.
+    pub active: GasParameters,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            governance_address: None,
+            active: GasParameters {
+                min_gas_price: 100_000_000_000,
+                storage_byte: 15,
+                consensus_deposit: 60_000,
+                consensus_withdraw: 60_000,
+                miner_cut: 0,
+            },
+        }
+    }
+}
+
+impl module::Parameters for Parameters {
+    type Error = Error;
+
+    fn validate_basic(&self) -> Result<(), Self::Error> {
+        self.active.validate()
+    }
+}
+
+/// Genesis state for the gas-parameters module.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct Genesis {
+    pub parameters: Parameters,
+}
+
+/// Configuration trait wiring the module to the runtime's concrete modules and authorities.
+pub trait Config: 'static {
+    /// Propagate a newly-activated gas-parameter set to the modules that own each knob, so the
+    /// active set is what every fee-computation path actually reads. Implemented by the runtime,
+    /// which knows the concrete module instantiations.
+    fn apply_gas_parameters(_params: &GasParameters) {}
+}
+
+/// Argument to the `gasParams.Update` call.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct Update {
+    /// Round at which the staged parameters become active. Must be strictly in the future.
+    pub active_round: u64,
+    /// Partial parameter update.
+    pub params: GasParameterUpdate,
+}
+
+/// State schema.
+///
+/// `0x01` holds the module parameters (including the active set); `0x02` holds the optional pending
+/// set awaiting promotion.
+const PENDING: &[u8] = &[0x02];
+
+/// The gas-parameters module.
+pub struct Module<Cfg: Config> {
+    _cfg: std::marker::PhantomData<Cfg>,
+}
+
+impl<Cfg: Config> module::Module for Module<Cfg> {
+    const NAME: &'static str = MODULE_NAME;
+    const VERSION: u32 = 1;
+    type Error = Error;
+    type Event = Event;
+    type Parameters = Parameters;
+    type Genesis = Genesis;
+}
+
+impl<Cfg: Config> Module<Cfg> {
+    /// Returns the active gas parameters.
+    pub fn active_params() -> GasParameters {
+        Self::params().active
+    }
+
+    fn pending_store() -> Option<Pending> {
+        CurrentState::with_store(|store| {
+            let store = storage::PrefixStore::new(store, &MODULE_NAME);
+            let tstore = storage::TypedStore::new(store);
+            tstore.get(PENDING)
+        })
+    }
+
+    fn set_pending(pending: Option<Pending>) {
+        CurrentState::with_store(|store| {
+            let store = storage::PrefixStore::new(store, &MODULE_NAME);
+            let mut tstore = storage::TypedStore::new(store);
+            match pending {
+                Some(p) => tstore.insert(PENDING, p),
+                None => tstore.remove(PENDING),
+            }
+        });
+    }
+
+    /// Returns whether `caller` is authorized to stage parameter updates.
+    fn is_authorized(caller: &Address) -> bool {
+        Self::params().governance_address.as_ref() == Some(caller)
+    }
+}
+
+#[sdk::sdk_derive(Module)]
+impl<Cfg: Config> Module<Cfg> {
+    /// Stage a (partial) gas-parameter update for promotion at a future round.
+    #[handler(call = "gasParams.Update")]
+    fn tx_update<C: Context>(ctx: &C, body: Update) -> Result<(), Error> {
+        let caller = CurrentState::with_env(|env| env.tx_caller_address());
+        if !Self::is_authorized(&caller) {
+            return Err(Error::Forbidden);
+        }
+
+        let round: u64 = ctx
+            .runtime_header()
+            .round
+            .checked_add(1)
+            .ok_or(Error::InvalidArgument)?;
+        if body.active_round < round {
+            return Err(Error::InvalidArgument);
+        }
+
+        let merged = Self::params().active.merge(body.params)?;
+        Self::set_pending(Some(Pending {
+            active_round: body.active_round,
+            params: merged,
+        }));
+
+        CurrentState::with(|state| {
+            state.emit_event(Event::Staged {
+                active_round: body.active_round,
+            })
+        });
+        Ok(())
+    }
+
+    /// Query the currently active gas parameters.
+    #[handler(query = "gasParams.Active")]
+    fn query_active<C: Context>(_ctx: &C, _args: ()) -> Result<GasParameters, Error> {
+        Ok(Self::active_params())
+    }
+
+    /// Promote a pending parameter set whose target round has been reached.
+    #[handler(call = "gasParams.Promote", internal)]
+    fn promote<C: Context>(ctx: &C, _args: ()) -> Result<(), Error> {
+        Self::maybe_promote(ctx);
+        Ok(())
+    }
+}
+
+impl<Cfg: Config> module::BlockHandler for Module<Cfg> {
+    fn begin_block<C: Context>(ctx: &C) {
+        Self::maybe_promote(ctx);
+    }
+}
+
+impl<Cfg: Config> Module<Cfg> {
+    /// Promote the pending set to active if its activation round has been reached.
+    fn maybe_promote<C: Context>(ctx: &C) {
+        let round = ctx.runtime_header().round;
+        let Some(pending) = Self::pending_store() else {
+            return;
+        };
+        if round < pending.active_round {
+            return;
+        }
+        let mut params = Self::params();
+        params.active = pending.params;
+        Self::set_params(params.clone());
+        // Write the promoted knobs through to the modules that own them so fee computation uses
+        // the new values, not just this module's stored copy.
+        Cfg::apply_gas_parameters(&params.active);
+        Self::set_pending(None);
+        CurrentState::with(|state| state.emit_event(Event::Promoted { round }));
+    }
+}
+
+impl<Cfg: Config> module::MigrationHandler for Module<Cfg> {
+    type Genesis = Genesis;
+
+    fn init_or_migrate<C: Context>(
+        _ctx: &C,
+        meta: &mut modules::core::types::Metadata,
+        genesis: Self::Genesis,
+    ) -> bool {
+        let version = meta.versions.get(MODULE_NAME).copied().unwrap_or_default();
+        if version == 0 {
+            genesis
+                .parameters
+                .validate_basic()
+                .expect("invalid genesis gas parameters");
+            Self::set_params(genesis.parameters);
+            meta.versions.insert(
+                MODULE_NAME.to_owned(),
+                <Self as module::Module>::VERSION,
+            );
+            return true;
+        }
+        false
+    }
+}
+
+impl<Cfg: Config> module::InvariantHandler for Module<Cfg> {}
+impl<Cfg: Config> module::TransactionHandler for Module<Cfg> {}
+impl<Cfg: Config> module::FeeProxyHandler for Module<Cfg> {}
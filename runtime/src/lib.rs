@@ -15,6 +15,12 @@ use oasis_runtime_sdk::{
 };
 use once_cell::unsync::Lazy;
 
+pub mod config_provider;
+pub mod fee_split;
+pub mod gas_params;
+pub mod migrations;
+pub mod precompiles;
+
 /// Configuration of the various modules.
 pub struct Config;
 
@@ -51,6 +57,16 @@ const fn state_version() -> u32 {
     }
 }
 
+/// Genesis values shared between the core/consensus modules and the governed gas-parameters active
+/// set. Keeping them in one place guarantees the active set agrees with the parameters the chain is
+/// actually charging on every network — including Mainnet, whose `STATE_VERSION` is 4 so none of the
+/// migration steps (which would otherwise seed the active set) run.
+const GENESIS_MIN_GAS_PRICE: u128 = 100_000_000_000;
+/// Storage byte gas cost installed at genesis; kept in sync with the gas-parameters active set.
+const GENESIS_STORAGE_BYTE: u64 = 15;
+/// Consensus deposit/withdraw gas costs installed at genesis; kept in sync with the active set.
+const GENESIS_CONSENSUS_OP_COST: u64 = 60_000;
+
 impl modules::core::Config for Config {
     /// Default local minimum gas price configuration that is used in case no overrides are set in
     /// local per-node configuration.
@@ -67,7 +83,10 @@ impl modules::core::Config for Config {
 }
 
 impl module_evm::Config for Config {
-    type AdditionalPrecompileSet = ();
+    /// Downstream-registered extra precompiles, layered on top of the confidential precompiles that
+    /// `module_evm` provides itself. The default set registers no extras (`()`), preserving the
+    /// previous behavior for this runtime.
+    type AdditionalPrecompileSet = precompiles::SapphirePrecompiles;
 
     const CHAIN_ID: u64 = chain_id();
 
@@ -97,6 +116,26 @@ impl modules::rofl::Config for Config {
         BaseUnits::new(10_000_000_000_000_000_000_000, Denomination::NATIVE);
 }
 
+impl gas_params::Config for Config {
+    /// Write a promoted gas-parameter set through to the modules that own each knob, so every
+    /// fee-computation path reads the active values.
+    fn apply_gas_parameters(params: &gas_params::GasParameters) {
+        // Core: minimum gas price and storage byte cost.
+        let mut core = modules::core::Module::<Config>::params();
+        core.min_gas_price
+            .insert(Denomination::NATIVE, params.min_gas_price);
+        core.gas_costs.storage_byte = params.storage_byte;
+        modules::core::Module::<Config>::set_params(core);
+
+        // Consensus accounts: deposit/withdraw costs.
+        let mut ca =
+            modules::consensus_accounts::Module::<modules::consensus::Module>::params();
+        ca.gas_costs.tx_deposit = params.consensus_deposit;
+        ca.gas_costs.tx_withdraw = params.consensus_withdraw;
+        modules::consensus_accounts::Module::<modules::consensus::Module>::set_params(ca);
+    }
+}
+
 /// The EVM ParaTime.
 pub struct Runtime;
 
@@ -129,12 +168,17 @@ impl sdk::Runtime for Runtime {
         modules::consensus::Module,
         // Consensus layer accounts.
         modules::consensus_accounts::Module<modules::consensus::Module>,
+        // Configurable fee split (miner cut) to block producers. Ordered before rewards so its cut
+        // is taken from the fee accumulator before the rewards/common-pool settlement consumes it.
+        fee_split::Module<Config>,
         // Rewards.
         modules::rewards::Module,
         // ROFL.
         modules::rofl::Module<Config>,
         // EVM.
         module_evm::Module<Config>,
+        // On-chain governed gas parameters.
+        gas_params::Module<Config>,
     );
 
     fn trusted_signers() -> Option<TrustedSigners> {
@@ -153,6 +197,10 @@ impl sdk::Runtime for Runtime {
         })
     }
 
+    // The consensus trust root is read by the SGX verifier before any runtime context exists, so it
+    // cannot be sourced from the SDK's (context-bound) local-config surface; it remains a compiled
+    // checkpoint. Operator-configurable network defaults are applied from local config in
+    // `migrate_state`, which does have a context.
     #[cfg(target_env = "sgx")]
     fn consensus_trust_root() -> Option<TrustRoot> {
         if is_testnet() {
@@ -182,7 +230,9 @@ impl sdk::Runtime for Runtime {
         (
             modules::core::Genesis {
                 parameters: modules::core::Parameters {
-                    min_gas_price: { BTreeMap::from([(Denomination::NATIVE, 100_000_000_000)]) },
+                    min_gas_price: {
+                        BTreeMap::from([(Denomination::NATIVE, GENESIS_MIN_GAS_PRICE)])
+                    },
                     dynamic_min_gas_price: modules::core::DynamicMinGasPrice {
                         enabled: true,
                         target_block_gas_usage_percentage: 50,
@@ -194,7 +244,7 @@ impl sdk::Runtime for Runtime {
                     max_multisig_signers: 8,
                     gas_costs: modules::core::GasCosts {
                         tx_byte: 1,
-                        storage_byte: 15,
+                        storage_byte: GENESIS_STORAGE_BYTE,
                         auth_signature: 1_000,
                         auth_multisig_signer: 1_000,
                         callformat_x25519_deoxysii: 10_000,
@@ -231,8 +281,8 @@ impl sdk::Runtime for Runtime {
             modules::consensus_accounts::Genesis {
                 parameters: modules::consensus_accounts::Parameters {
                     gas_costs: modules::consensus_accounts::GasCosts {
-                        tx_deposit: 60_000,
-                        tx_withdraw: 60_000,
+                        tx_deposit: GENESIS_CONSENSUS_OP_COST,
+                        tx_withdraw: GENESIS_CONSENSUS_OP_COST,
                         tx_delegate: 60_000,
                         tx_undelegate: 120_000,
 
@@ -245,6 +295,8 @@ impl sdk::Runtime for Runtime {
                     disable_withdraw: false,
                 },
             },
+            // Fee split has no parameters of its own; the miner cut lives in gas_params.
+            (),
             modules::rewards::Genesis {
                 parameters: modules::rewards::Parameters {
                     schedule: modules::rewards::types::RewardSchedule {
@@ -266,28 +318,50 @@ impl sdk::Runtime for Runtime {
                     gas_costs: module_evm::GasCosts {},
                 },
             },
+            gas_params::Genesis {
+                parameters: gas_params::Parameters {
+                    // The governance address is operator-configured via node-local config and
+                    // applied in `migrate_state`; genesis leaves it unset so updates are disabled
+                    // until an operator opts in.
+                    governance_address: None,
+                    // Seed the active set from the same genesis values the core/consensus modules
+                    // install so every fee-computation path reads one consistent set from block one.
+                    // This is the only thing that reconciles the active set on Mainnet, where no
+                    // migration step runs (STATE_VERSION is 4).
+                    active: gas_params::GasParameters {
+                        min_gas_price: GENESIS_MIN_GAS_PRICE,
+                        storage_byte: GENESIS_STORAGE_BYTE,
+                        consensus_deposit: GENESIS_CONSENSUS_OP_COST,
+                        consensus_withdraw: GENESIS_CONSENSUS_OP_COST,
+                        miner_cut: 0,
+                    },
+                },
+            },
         )
     }
 
-    fn migrate_state<C: sdk::Context>(_ctx: &C) {
-        // State migration from by copying over parameters from updated genesis state.
-        let genesis = Self::genesis_state();
+    fn migrate_state<C: sdk::Context>(ctx: &C) {
+        // Apply incremental migration steps in sequence from the stored version up to the target
+        // `STATE_VERSION`, preserving any parameters that were changed on-chain. Each step owns the
+        // fields it touches rather than wholesale-overwriting from genesis.
+        let target = Self::STATE_VERSION;
+        let from = migrations::stored_version();
+        migrations::run(ctx, from, target);
+        migrations::set_stored_version(target);
 
-        // Core.
-        modules::core::Module::<Config>::set_params(genesis.0.parameters);
-        // Accounts.
-        modules::accounts::Module::set_params(genesis.1.parameters);
-        // Consensus layer interface.
-        modules::consensus::Module::set_params(genesis.2.parameters);
-        // Consensus layer accounts.
-        modules::consensus_accounts::Module::<modules::consensus::Module>::set_params(
-            genesis.3.parameters,
-        );
-        // Rewards.
-        modules::rewards::Module::set_params(genesis.4.parameters);
-        // ROFL.
-        modules::rofl::Module::<Config>::set_params(genesis.5.parameters);
-        // EVM.
-        module_evm::Module::<Config>::set_params(genesis.6.parameters);
+        // Apply operator-configurable network defaults from the node's local configuration. These
+        // cannot be set from `genesis_state`/`consensus_trust_root` (no context there), so they are
+        // reconciled here, where a context exists. Absent values leave the on-chain state untouched.
+        let local = config_provider::local_config(ctx);
+        if let Some(min_gas_price) = local.min_gas_price {
+            let mut core = modules::core::Module::<Config>::params();
+            core.min_gas_price.insert(Denomination::NATIVE, min_gas_price);
+            modules::core::Module::<Config>::set_params(core);
+        }
+        if let Some(addr) = local.gas_governance_address {
+            let mut gp = gas_params::Module::<Config>::params();
+            gp.governance_address = Some(addr);
+            gas_params::Module::<Config>::set_params(gp);
+        }
     }
 }
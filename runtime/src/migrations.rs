@@ -0,0 +1,125 @@
+//! Versioned incremental state migrations.
+//!
+//! Instead of clobbering every module's parameters from `genesis_state()` on each upgrade, the
+//! runtime keeps an ordered registry of migration steps keyed by the *source* `STATE_VERSION`. A
+//! node that was last at version `N` applies steps `N → N+1`, `N+1 → N+2`, … up to
+//! [`Runtime::STATE_VERSION`], each in turn. A step may rewrite parameters *and* walk or re-encode
+//! stored data, but only touches the fields it owns — values set on-chain (e.g. via governance) are
+//! preserved across the upgrade.
+//!
+//! [`Runtime::STATE_VERSION`]: oasis_runtime_sdk::Runtime::STATE_VERSION
+use std::collections::BTreeMap;
+
+use oasis_runtime_sdk::{modules, state::CurrentState, storage, Context};
+
+use crate::Config;
+
+/// Storage prefix under which the runtime records the last applied state version.
+const MODULE_NAME: &str = "migrations";
+/// State key holding the last applied state version as a CBOR-encoded `u32`.
+const APPLIED_VERSION: &[u8] = &[0x01];
+
+/// A single migration step, run with the runtime context for the block performing the upgrade.
+pub type MigrationFn<C> = fn(&C);
+
+/// Lowest source version for which a migration step is registered. Must stay in sync with
+/// [`registry`]; used as the conservative floor when no prior version has been recorded yet.
+const EARLIEST_SOURCE_VERSION: u32 = 4;
+
+/// Returns the migration registry keyed by the source state version. Step `k` upgrades state from
+/// version `k` to version `k + 1`.
+fn registry<C: Context>() -> BTreeMap<u32, MigrationFn<C>> {
+    let mut steps: BTreeMap<u32, MigrationFn<C>> = BTreeMap::new();
+    steps.insert(4, migrate_v4_to_v5::<C>);
+    steps.insert(5, migrate_v5_to_v6::<C>);
+    steps.insert(6, migrate_v6_to_v7::<C>);
+    steps
+}
+
+/// Run every registered migration step in order from `from` (the stored version) up to `to` (the
+/// target [`Runtime::STATE_VERSION`]). Unknown intermediate versions are skipped, so a gap in the
+/// registry is a no-op rather than an error.
+///
+/// [`Runtime::STATE_VERSION`]: oasis_runtime_sdk::Runtime::STATE_VERSION
+pub fn run<C: Context>(ctx: &C, from: u32, to: u32) {
+    let steps = registry::<C>();
+    for version in from..to {
+        if let Some(step) = steps.get(&version) {
+            step(ctx);
+        }
+    }
+}
+
+/// v4 → v5: introduce the dynamic minimum gas price controller while preserving the operator's
+/// configured `min_gas_price` and all other core parameters. Idempotent: if a controller has
+/// already been configured (enabled) it is left untouched so governance-set values survive a
+/// conservative re-run.
+fn migrate_v4_to_v5<C: Context>(_ctx: &C) {
+    let mut params = modules::core::Module::<Config>::params();
+    if params.dynamic_min_gas_price.enabled {
+        return;
+    }
+    params.dynamic_min_gas_price = modules::core::DynamicMinGasPrice {
+        enabled: true,
+        target_block_gas_usage_percentage: 50,
+        min_price_max_change_denominator: 8,
+    };
+    modules::core::Module::<Config>::set_params(params);
+}
+
+/// v5 → v6: raise the storage byte gas cost to its current default (15), but only if it is below
+/// that default; a value already at or above 15 is kept.
+fn migrate_v5_to_v6<C: Context>(_ctx: &C) {
+    let mut params = modules::core::Module::<Config>::params();
+    if params.gas_costs.storage_byte < 15 {
+        params.gas_costs.storage_byte = 15;
+        modules::core::Module::<Config>::set_params(params);
+    }
+}
+
+/// v6 → v7: backfill ROFL parameters added after the original genesis without disturbing any
+/// registered applications or previously-set fields.
+fn migrate_v6_to_v7<C: Context>(_ctx: &C) {
+    // The ROFL module's parameters gained fields with safe defaults; re-persist the stored set so
+    // the newly-added fields are materialized without overwriting existing values.
+    let params = modules::rofl::Module::<Config>::params();
+    modules::rofl::Module::<Config>::set_params(params);
+
+    // Activate the gas-parameters subsystem with defaults derived from the live core and consensus
+    // accounts parameters so
+    // the governed active set matches what the chain is currently charging.
+    let core = modules::core::Module::<Config>::params();
+    let mut gp = crate::gas_params::Module::<Config>::params();
+    gp.active.storage_byte = core.gas_costs.storage_byte;
+    if let Some((_, price)) = core.min_gas_price.iter().next() {
+        gp.active.min_gas_price = *price;
+    }
+    let ca = modules::consensus_accounts::Module::<modules::consensus::Module>::params();
+    gp.active.consensus_deposit = ca.gas_costs.tx_deposit;
+    gp.active.consensus_withdraw = ca.gas_costs.tx_withdraw;
+    crate::gas_params::Module::<Config>::set_params(gp);
+}
+
+/// Determine the state version to migrate *from*.
+///
+/// Returns the version this runtime last recorded. On the very first upgrade under this migration
+/// system no version has been recorded yet — exactly the upgrade that still needs its steps run —
+/// so we fall back to [`EARLIEST_SOURCE_VERSION`] rather than the target version. Every step is
+/// idempotent and preserves already-set values, so conservatively starting from the earliest
+/// source is safe and guarantees no step (e.g. `v6 → v7`) is skipped.
+pub fn stored_version() -> u32 {
+    CurrentState::with_store(|store| {
+        let store = storage::PrefixStore::new(store, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(APPLIED_VERSION).unwrap_or(EARLIEST_SOURCE_VERSION)
+    })
+}
+
+/// Record `version` as the last applied state version.
+pub fn set_stored_version(version: u32) {
+    CurrentState::with_store(|store| {
+        let store = storage::PrefixStore::new(store, &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(store);
+        tstore.insert(APPLIED_VERSION, version);
+    });
+}
@@ -0,0 +1,153 @@
+//! Composable additional precompile set for the Sapphire EVM.
+//!
+//! `module_evm` exposes an `AdditionalPrecompileSet` associated type that Sapphire previously left
+//! as `()`, so the only way to add a host function was to fork the crate. This module provides a
+//! concrete [`SapphirePrecompiles`] set that implements the EVM's [`PrecompileSet`] interface and
+//! lets downstream runtimes register *extra* precompiles keyed by address behind the stable
+//! [`Precompile`] trait. Sapphire's built-in confidential precompiles (random bytes, X25519,
+//! Deoxys-II, signature schemes) are provided by `module_evm` itself; this set only layers
+//! additional precompiles on top and returns `None` for everything else so the EVM falls through to
+//! those built-ins.
+//!
+//! Reserved addresses (the confidential range Sapphire already occupies) may not be re-registered:
+//! the [`reserve`] helper is a `const fn` so a custom set that collides with a reserved address
+//! fails to compile. Each precompile charges its gas through the EVM handle's `record_cost`, which
+//! `module_evm` meters against `modules::core`, so extra host functions cannot escape the batch gas
+//! budget.
+use std::marker::PhantomData;
+
+use evm::{
+    executor::stack::{
+        IsPrecompileResult, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+        PrecompileSet,
+    },
+    ExitSucceed,
+};
+use primitive_types::H160;
+
+/// The reserved confidential precompile address range `[0x0100, 0x01ff]`. Sapphire's built-in
+/// precompiles live here and custom registrations must not overlap it.
+pub const RESERVED_RANGE: (u64, u64) = (0x0100, 0x01ff);
+
+/// A single additional precompile: its fixed address, a gas estimate charged before execution, and
+/// the execution entry point.
+pub trait Precompile {
+    /// The address at which this precompile is exposed.
+    const ADDRESS: H160;
+
+    /// Gas charged for a call over `input`. Recorded via the EVM handle before `run`, so the cost
+    /// counts against the batch gas budget metered by `modules::core`.
+    fn gas_cost(input: &[u8]) -> u64;
+
+    /// Execute the precompile over `input`, returning its output bytes.
+    fn run(input: &[u8]) -> Result<Vec<u8>, PrecompileFailure>;
+
+    /// Charge gas and execute, producing the EVM precompile output. Shared by the registry so every
+    /// precompile is metered identically.
+    fn dispatch(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        handle
+            .record_cost(Self::gas_cost(&input))
+            .map_err(|exit_status| PrecompileFailure::Error { exit_status })?;
+        let output = Self::run(&input)?;
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+}
+
+/// Compile-time guard: reserve `low` for a custom precompile, panicking at const-evaluation time
+/// (i.e. failing the build) if it falls inside [`RESERVED_RANGE`]. Use it to derive the `ADDRESS`
+/// const of a downstream precompile so collisions are caught before the runtime ever starts.
+///
+/// ```ignore
+/// const MY_ADDR: H160 = addr_from_low(precompiles::reserve(0x0a01));
+/// ```
+pub const fn reserve(low: u64) -> u64 {
+    assert!(
+        low < RESERVED_RANGE.0 || low > RESERVED_RANGE.1,
+        "custom precompile address collides with the reserved confidential range"
+    );
+    low
+}
+
+/// Build an [`H160`] from the low 64 bits, matching how EVM precompile addresses are laid out.
+pub const fn addr_from_low(low: u64) -> H160 {
+    let mut bytes = [0u8; 20];
+    let low = low.to_be_bytes();
+    bytes[12] = low[0];
+    bytes[13] = low[1];
+    bytes[14] = low[2];
+    bytes[15] = low[3];
+    bytes[16] = low[4];
+    bytes[17] = low[5];
+    bytes[18] = low[6];
+    bytes[19] = low[7];
+    H160(bytes)
+}
+
+/// Trait implemented by a cons-list of [`Precompile`]s to expose them as a registry. Downstream
+/// runtimes assemble their precompiles as a tuple `(A, (B, (C, ())))` and plug it in as `Extra`.
+pub trait PrecompileRegistry {
+    /// Execute the precompile owning the handle's `code_address`, if any.
+    fn execute(handle: &mut impl PrecompileHandle) -> Option<PrecompileResult>;
+
+    /// Whether `address` is owned by this registry.
+    fn is_precompile(address: H160) -> bool;
+}
+
+/// The empty registry contributes no precompiles.
+impl PrecompileRegistry for () {
+    fn execute(_handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+        None
+    }
+    fn is_precompile(_address: H160) -> bool {
+        false
+    }
+}
+
+/// Recursively dispatch across the cons-list.
+impl<P: Precompile, Rest: PrecompileRegistry> PrecompileRegistry for (P, Rest) {
+    fn execute(handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+        if handle.code_address() == P::ADDRESS {
+            Some(P::dispatch(handle))
+        } else {
+            Rest::execute(handle)
+        }
+    }
+
+    fn is_precompile(address: H160) -> bool {
+        address == P::ADDRESS || Rest::is_precompile(address)
+    }
+}
+
+/// A set of additional precompiles. `Extra` is a downstream [`PrecompileRegistry`]; its members are
+/// layered on top of Sapphire's built-in confidential precompiles. The default `()` contributes
+/// nothing, preserving the previous behavior.
+pub struct SapphirePrecompiles<Extra = ()> {
+    _extra: PhantomData<Extra>,
+}
+
+impl<Extra> Default for SapphirePrecompiles<Extra> {
+    fn default() -> Self {
+        Self {
+            _extra: PhantomData,
+        }
+    }
+}
+
+impl<Extra: PrecompileRegistry> PrecompileSet for SapphirePrecompiles<Extra> {
+    fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+        // Only the additional, downstream-registered precompiles are handled here. Returning `None`
+        // for any other address lets `module_evm` reach its built-in confidential precompiles.
+        Extra::execute(handle)
+    }
+
+    fn is_precompile(&self, address: H160, _remaining_gas: u64) -> IsPrecompileResult {
+        IsPrecompileResult::Answer {
+            is_precompile: Extra::is_precompile(address),
+            extra_cost: 0,
+        }
+    }
+}